@@ -1,8 +1,8 @@
 use std::fs::File;
 use std::io;
-use std::io::{BufRead, Seek, Write};
+use std::io::{BufRead, Seek, SeekFrom, Write};
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use parking_lot::Mutex;
@@ -34,11 +34,30 @@ fn line_error(line_number: usize, line: &str) -> Error {
     Error::Read(format!("Invalid data as line {line_number}: `{line}`"))
 }
 
+/// Once dead bytes (superseded or tombstoned records) pass this fraction of the file's
+/// total size, the next `set`/`unset` triggers a compaction.
+const DEFAULT_COMPACTION_THRESHOLD: f64 = 0.5;
+
+/// A Bitcask-style append-only store: every `set`/`unset` is a new record appended to the
+/// log, and an in-memory keydir maps each key to the byte offset of its latest record so
+/// `get` never has to scan the file.
 #[derive(Clone)]
 pub struct Store<T>(Arc<Mutex<StoreInner<T>>>);
 
+/// Where a record lives in the log and how many bytes (including its trailing newline) it
+/// takes up, so dead space left behind by an overwritten record can be tallied.
+#[derive(Clone, Copy)]
+struct KeydirEntry {
+    offset: u64,
+    len: u64,
+}
+
 struct StoreInner<T> {
+    path: PathBuf,
     file: File,
+    keydir: FxHashMap<String, KeydirEntry>,
+    live_bytes: u64,
+    dead_bytes: u64,
     _phantom: PhantomData<T>,
 }
 
@@ -47,16 +66,22 @@ where
     T: Serialize + for<'a> Deserialize<'a>,
 {
     pub fn open(path: &Path) -> io::Result<Self> {
-        let file = File::options()
+        let mut file = File::options()
             .read(true)
             .write(true)
             .create(true)
             .append(true)
             .open(path)?;
 
+        let (keydir, live_bytes, dead_bytes) = build_keydir(&mut file)?;
+
         let inner = StoreInner {
+            path: path.to_path_buf(),
             file,
-            _phantom: PhantomData::default(),
+            keydir,
+            live_bytes,
+            dead_bytes,
+            _phantom: PhantomData,
         };
 
         Ok(Store(Arc::new(Mutex::new(inner))))
@@ -65,37 +90,49 @@ where
     pub fn set(&self, key: &str, data: &T) -> Result<(), Error> {
         let key = validate_key(key)?;
         let mut inner = self.0.lock();
+
         let data = serde_json::to_string(&Some(data)).map_err(write_err)?;
-        writeln!(inner.file, "{key},{data}").map_err(write_err)
+        let record = format!("{key},{data}");
+        let entry = append_record(&mut inner.file, &record)?;
+
+        if let Some(old) = inner.keydir.insert(key.to_string(), entry) {
+            inner.live_bytes -= old.len;
+            inner.dead_bytes += old.len;
+        }
+        inner.live_bytes += entry.len;
+
+        inner.maybe_compact()
     }
 
     pub fn unset(&self, key: &str) -> Result<(), Error> {
         let key = validate_key(key)?;
         let mut inner = self.0.lock();
+
         let data = serde_json::to_string(&Option::<T>::None).map_err(write_err)?;
-        writeln!(inner.file, "{key},{data}").map_err(write_err)
+        let record = format!("{key},{data}");
+        let entry = append_record(&mut inner.file, &record)?;
+        inner.dead_bytes += entry.len;
+
+        if let Some(old) = inner.keydir.remove(key) {
+            inner.live_bytes -= old.len;
+            inner.dead_bytes += old.len;
+        }
+
+        inner.maybe_compact()
     }
 
     pub fn get(&self, key: &str) -> Result<Option<T>, Error> {
         let key = validate_key(key)?;
-
         let mut inner = self.0.lock();
-        inner.file.rewind().map_err(read_err)?;
-
-        let mut value = None;
-
-        let reader = io::BufReader::new(&inner.file);
-        for (line_number, line) in reader.lines().enumerate() {
-            let line = line.map_err(read_err)?;
 
-            let (k, v) = split_key_value(&line, line_number)?;
+        let Some(entry) = inner.keydir.get(key).copied() else {
+            return Ok(None);
+        };
 
-            if k == key {
-                value = serde_json::from_str(v).map_err(read_err)?;
-            }
-        }
+        let line = read_record(&mut inner.file, entry)?;
+        let (_, v) = split_key_value(&line, 0)?;
 
-        Ok(value)
+        serde_json::from_str(v).map_err(read_err)
     }
 
     pub fn to_map(&self) -> Result<FxHashMap<String, T>, Error> {
@@ -118,10 +155,132 @@ where
 
         Ok(map)
     }
+
+    /// Rewrites the log with only the records the keydir still considers live, reclaiming
+    /// the space taken up by overwritten and tombstoned records. Happens automatically once
+    /// dead bytes pass the compaction threshold, but can also be called directly.
+    pub fn compact(&self) -> Result<(), Error> {
+        self.0.lock().compact()
+    }
+}
+
+impl<T> StoreInner<T> {
+    fn maybe_compact(&mut self) -> Result<(), Error> {
+        let total_bytes = self.live_bytes + self.dead_bytes;
+        if total_bytes > 0
+            && self.dead_bytes as f64 > total_bytes as f64 * DEFAULT_COMPACTION_THRESHOLD
+        {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    fn compact(&mut self) -> Result<(), Error> {
+        let tmp_path = self.path.with_extension("compact");
+        let mut tmp_file = File::create(&tmp_path).map_err(write_err)?;
+
+        let mut keys: Vec<_> = self.keydir.iter().map(|(k, e)| (k.clone(), *e)).collect();
+        keys.sort_by_key(|(_, entry)| entry.offset);
+
+        let mut new_keydir = FxHashMap::default();
+        let mut offset = 0u64;
+
+        for (key, entry) in keys {
+            let line = read_record(&mut self.file, entry)?;
+            writeln!(tmp_file, "{line}").map_err(write_err)?;
+
+            let len = line.len() as u64 + 1;
+            new_keydir.insert(key, KeydirEntry { offset, len });
+            offset += len;
+        }
+
+        tmp_file.flush().map_err(write_err)?;
+        drop(tmp_file);
+
+        std::fs::rename(&tmp_path, &self.path).map_err(write_err)?;
+
+        self.file = File::options()
+            .read(true)
+            .write(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(write_err)?;
+        self.live_bytes = offset;
+        self.dead_bytes = 0;
+        self.keydir = new_keydir;
+
+        Ok(())
+    }
+}
+
+/// Appends `record` as a new line and returns where it landed in the log.
+fn append_record(file: &mut File, record: &str) -> Result<KeydirEntry, Error> {
+    let offset = file.seek(SeekFrom::End(0)).map_err(write_err)?;
+    writeln!(file, "{record}").map_err(write_err)?;
+
+    Ok(KeydirEntry {
+        offset,
+        len: record.len() as u64 + 1,
+    })
+}
+
+/// Reads back the single line at `entry`'s offset, without the trailing newline.
+fn read_record(file: &mut File, entry: KeydirEntry) -> Result<String, Error> {
+    file.seek(SeekFrom::Start(entry.offset)).map_err(read_err)?;
+
+    let mut line = String::new();
+    io::BufReader::new(&*file)
+        .read_line(&mut line)
+        .map_err(read_err)?;
+
+    if line.ends_with('\n') {
+        line.pop();
+    }
+
+    Ok(line)
+}
+
+/// Scans the whole log once, building a keydir that maps each key to the offset of its
+/// latest record, and tallying live vs. dead bytes along the way.
+fn build_keydir(file: &mut File) -> io::Result<(FxHashMap<String, KeydirEntry>, u64, u64)> {
+    file.rewind()?;
+
+    let mut keydir = FxHashMap::default();
+    let mut dead_bytes = 0u64;
+    let mut offset = 0u64;
+
+    let reader = io::BufReader::new(&*file);
+    for line in reader.lines() {
+        let line = line?;
+        let len = line.len() as u64 + 1;
+
+        if let Ok((key, value)) = split_key_value(&line, 0) {
+            let is_tombstone = value == "null";
+
+            let previous = if is_tombstone {
+                keydir.remove(key)
+            } else {
+                keydir.insert(key.to_string(), KeydirEntry { offset, len })
+            };
+
+            if let Some(previous) = previous {
+                dead_bytes += previous.len;
+            }
+            if is_tombstone {
+                dead_bytes += len;
+            }
+        }
+
+        offset += len;
+    }
+
+    let live_bytes: u64 = keydir.values().map(|entry| entry.len).sum();
+
+    Ok((keydir, live_bytes, dead_bytes))
 }
 
 fn split_key_value(line: &str, line_number: usize) -> Result<(&str, &str), Error> {
-    let mut split = line.split(',');
+    let mut split = line.splitn(2, ',');
     let k = split.next().ok_or_else(|| line_error(line_number, line))?;
     let v = split.next().ok_or_else(|| line_error(line_number, line))?;
 
@@ -176,4 +335,81 @@ mod tests {
         assert!(validate_key("this,is,a,bad,key").is_err());
         assert!(validate_key("this is\nalso bad").is_err());
     }
+
+    #[test]
+    fn compaction_reclaims_dead_space() {
+        let f = NamedTempFile::new().unwrap();
+        let store = Store::<u32>::open(f.path()).unwrap();
+
+        // `other` is set and cleared first: its tombstone pushes dead bytes past the
+        // threshold and compacts automatically, which would leave `compact` nothing to reclaim
+        // if it came last. The loop then ends with one superseded `key` record still in the log.
+        store.set("other", &0).unwrap();
+        store.unset("other").unwrap();
+        for i in 0..1_000 {
+            store.set("key", &i).unwrap();
+        }
+
+        let size_before = std::fs::metadata(f.path()).unwrap().len();
+        store.compact().unwrap();
+        let size_after = std::fs::metadata(f.path()).unwrap().len();
+
+        assert!(size_after < size_before);
+        assert_eq!(Some(999), store.get("key").unwrap());
+        assert_eq!(None, store.get("other").unwrap());
+    }
+
+    #[test]
+    fn automatic_compaction_keeps_the_log_bounded() {
+        let f = NamedTempFile::new().unwrap();
+        let store = Store::<u32>::open(f.path()).unwrap();
+
+        for i in 0..2_000 {
+            store.set("key", &i).unwrap();
+        }
+
+        let size = std::fs::metadata(f.path()).unwrap().len();
+        assert!(size < 2_000, "log grew unbounded: {size} bytes");
+        assert_eq!(Some(1_999), store.get("key").unwrap());
+    }
+
+    #[test]
+    fn round_trips_multi_field_values_with_commas() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Record {
+            channel_id: u64,
+            user_id: u64,
+            text: String,
+        }
+
+        let f = NamedTempFile::new().unwrap();
+        let store = Store::<Record>::open(f.path()).unwrap();
+
+        let record = Record {
+            channel_id: 111,
+            user_id: 222,
+            text: "hi, there".to_string(),
+        };
+        store.set("abc123", &record).unwrap();
+
+        assert_eq!(Some(&record), store.to_map().unwrap().get("abc123"));
+        assert_eq!(Some(record), store.get("abc123").unwrap());
+    }
+
+    #[test]
+    fn reopening_rebuilds_the_keydir() {
+        let f = NamedTempFile::new().unwrap();
+
+        {
+            let store = Store::<u32>::open(f.path()).unwrap();
+            store.set("a", &1).unwrap();
+            store.set("b", &2).unwrap();
+            store.set("a", &3).unwrap();
+            store.unset("b").unwrap();
+        }
+
+        let store = Store::<u32>::open(f.path()).unwrap();
+        assert_eq!(Some(3), store.get("a").unwrap());
+        assert_eq!(None, store.get("b").unwrap());
+    }
 }
@@ -1,15 +1,25 @@
+use crate::commands::{self, go, remind, Command};
 use crate::config::{Config, Feature};
+use crate::message_cache::{CachedMessage, MessageCache};
+use crate::responses;
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use parking_lot::RwLock;
 use serenity::async_trait;
-use serenity::model::channel::Reaction;
+use serenity::model::channel::{Message, Reaction};
+use serenity::model::event::MessageUpdateEvent;
 use serenity::model::gateway::Ready;
+use serenity::model::prelude::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::prelude::interaction::message_component::MessageComponentInteraction;
 use serenity::model::prelude::interaction::{Interaction, InteractionResponseType};
 use serenity::model::prelude::{ChannelId, GuildId, MessageId, ReactionType, RoleId, UserId};
 use serenity::prelude::*;
 
+/// How many mention-carrying messages to keep in memory for ghost-ping detection.
+const GHOST_PING_CACHE_CAPACITY: usize = 2000;
+
 pub async fn run(token: String, guild_id: GuildId, config: Arc<RwLock<Config>>) {
     // Set gateway intents, which decides what events the bot will be notified about
     let intents = GatewayIntents::GUILD_MESSAGES
@@ -17,11 +27,24 @@ pub async fn run(token: String, guild_id: GuildId, config: Arc<RwLock<Config>>)
         | GatewayIntents::DIRECT_MESSAGES
         | GatewayIntents::MESSAGE_CONTENT;
 
+    let link_store =
+        kv::Store::open(&config.read().link_store_path).expect("Unable to open link store");
+    let reminder_store = kv::Store::open(&config.read().reminder_store_path)
+        .expect("Unable to open reminder store");
+
     // Create a new instance of the Client, logging in as a bot. This will
     // automatically prepend your bot token with "Bot ", which is a requirement
     // by Discord for bot users.
     let mut client = Client::builder(&token, intents)
-        .event_handler(Handler { guild_id, config })
+        .event_handler(Handler {
+            guild_id,
+            config,
+            link_store,
+            reminder_store,
+            reminder_dispatcher_started: AtomicBool::new(false),
+            message_cache: MessageCache::with_capacity(GHOST_PING_CACHE_CAPACITY),
+            commands: commands::registry(),
+        })
         .await
         .expect("Err creating client");
 
@@ -37,12 +60,11 @@ pub async fn run(token: String, guild_id: GuildId, config: Arc<RwLock<Config>>)
 struct Handler {
     guild_id: GuildId,
     config: Arc<RwLock<Config>>,
-}
-
-impl Handler {
-    fn is_enabled(&self, feature: Feature) -> bool {
-        self.config.read().enabled_features.contains(&feature)
-    }
+    link_store: kv::Store<String>,
+    reminder_store: kv::Store<remind::Reminder>,
+    reminder_dispatcher_started: AtomicBool,
+    message_cache: MessageCache,
+    commands: Vec<Box<dyn Command>>,
 }
 
 #[async_trait]
@@ -99,27 +121,101 @@ impl EventHandler for Handler {
         }
     }
 
-    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::ApplicationCommand(command) = interaction {
-            log::info!("Received {} command", command.data.name);
+    async fn message(&self, _ctx: Context, msg: Message) {
+        if !self.is_enabled(Feature::GhostPings) {
+            return;
+        }
 
-            let response_data = match command.data.name.as_str() {
-                "ping" => "pong",
-                "codefmt" => CODE_FMT_MSG,
-                _ => "command not yet implemented",
-            };
+        if msg.mentions.is_empty() && msg.mention_roles.is_empty() {
+            return;
+        }
 
-            let result = command
-                .create_interaction_response(&ctx.http, |response| {
-                    response
-                        .kind(InteractionResponseType::ChannelMessageWithSource)
-                        .interaction_response_data(|message| message.content(response_data))
-                })
+        self.message_cache.insert(
+            msg.id,
+            CachedMessage {
+                author_id: msg.author.id,
+                channel_id: msg.channel_id,
+                content: msg.content,
+                mentioned_users: msg.mentions.iter().map(|user| user.id).collect(),
+                mentioned_roles: msg.mention_roles,
+            },
+        );
+    }
+
+    async fn message_delete(
+        &self,
+        ctx: Context,
+        _channel_id: ChannelId,
+        deleted_message_id: MessageId,
+        _guild_id: Option<GuildId>,
+    ) {
+        if !self.is_enabled(Feature::GhostPings) {
+            return;
+        }
+
+        if let Some(cached) = self.message_cache.remove(&deleted_message_id) {
+            self.report_ghost_ping(&ctx, "deleted", &cached).await;
+        }
+    }
+
+    async fn message_update(
+        &self,
+        ctx: Context,
+        _old_if_available: Option<Message>,
+        _new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        if !self.is_enabled(Feature::GhostPings) {
+            return;
+        }
+
+        let Some(cached) = self.message_cache.get(&event.id) else {
+            return;
+        };
+
+        let remaining_users: Vec<UserId> = event
+            .mentions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|user| user.id)
+            .collect();
+        let remaining_roles = event.mention_roles.unwrap_or_default();
+
+        let stripped_a_mention = cached
+            .mentioned_users
+            .iter()
+            .any(|id| !remaining_users.contains(id))
+            || cached
+                .mentioned_roles
+                .iter()
+                .any(|id| !remaining_roles.contains(id));
+
+        if stripped_a_mention {
+            self.message_cache.remove(&event.id);
+            self.report_ghost_ping(&ctx, "edited to remove a mention from", &cached)
                 .await;
+        } else if let Some(content) = &event.content {
+            self.message_cache.insert(
+                event.id,
+                CachedMessage {
+                    content: content.clone(),
+                    mentioned_users: remaining_users,
+                    mentioned_roles: remaining_roles,
+                    ..cached
+                },
+            );
+        }
+    }
 
-            if let Err(e) = result {
-                log::error!("Unable to respond to command: {e}");
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        match interaction {
+            Interaction::ApplicationCommand(command) => {
+                self.handle_application_command(ctx, command).await
+            }
+            Interaction::MessageComponent(component) => {
+                self.handle_message_component(ctx, component).await
             }
+            _ => {}
         }
     }
 
@@ -129,15 +225,10 @@ impl EventHandler for Handler {
         let result = self
             .guild_id
             .set_application_commands(&ctx.http, |commands| {
+                for command in &self.commands {
+                    commands.create_application_command(|c| command.register(c));
+                }
                 commands
-                    .create_application_command(|command| {
-                        command.name("ping").description("A ping command")
-                    })
-                    .create_application_command(|command| {
-                        command
-                            .name("codefmt")
-                            .description("Display a message showing how to share code samples")
-                    })
             })
             .await;
 
@@ -145,6 +236,345 @@ impl EventHandler for Handler {
             log::error!("Unable to create commands: {e}");
             std::process::exit(1);
         }
+
+        if self.is_enabled(Feature::Reminders) {
+            let already_started = self.reminder_dispatcher_started.swap(true, Ordering::SeqCst);
+            if !already_started {
+                remind::spawn_dispatcher(ctx, self.reminder_store.clone()).await;
+            }
+        }
+    }
+}
+
+impl Handler {
+    fn is_enabled(&self, feature: Feature) -> bool {
+        self.config.read().is_enabled(feature)
+    }
+
+    async fn report_ghost_ping(&self, ctx: &Context, action: &str, cached: &CachedMessage) {
+        let moderator_channel_id = self.config.read().moderator_channel_id;
+
+        let mentions = cached
+            .mentioned_users
+            .iter()
+            .map(|id| format!("<@{id}>"))
+            .chain(cached.mentioned_roles.iter().map(|id| format!("<@&{id}>")))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let content = responses::truncate(&cached.content, responses::MAX_EMBED_FIELD_VALUE_LEN);
+
+        let result = moderator_channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title("Ghost ping detected")
+                        .description(format!(
+                            "<@{}> {action} a message in <#{}> that mentioned {mentions}",
+                            cached.author_id, cached.channel_id
+                        ))
+                        .field("Content", content, false)
+                })
+            })
+            .await;
+
+        if let Err(e) = result {
+            log::error!("Unable to report ghost ping: {e}");
+        }
+    }
+
+    async fn handle_application_command(
+        &self,
+        ctx: Context,
+        command: ApplicationCommandInteraction,
+    ) {
+        let Some(handler) = self
+            .commands
+            .iter()
+            .find(|c| c.name() == command.data.name.as_str())
+        else {
+            let response = commands::Response::text("command not yet implemented");
+            return self.respond(&ctx, &command, response).await;
+        };
+
+        let caller_roles = command
+            .member
+            .as_ref()
+            .map(|member| member.roles.clone())
+            .unwrap_or_default();
+
+        let config = self.config.read().clone();
+
+        if let Some(denial) = commands::before(&command, &caller_roles, &config) {
+            return self.respond(&ctx, &command, commands::Response::text(denial)).await;
+        }
+
+        let state = commands::State {
+            link_store: &self.link_store,
+            reminder_store: &self.reminder_store,
+            config: &config,
+        };
+
+        let response = handler.run(&ctx, &command, &state).await;
+        commands::after(handler.name(), &response);
+        self.respond(&ctx, &command, response).await;
+    }
+
+    /// Sends `response`, splitting its content across a followup per chunk when it's too
+    /// long for a single message. Only the first chunk carries `response.action`'s
+    /// components, since they belong to a single message.
+    async fn respond(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        response: commands::Response,
+    ) {
+        let action = match response.action {
+            commands::Action::Fields { title, fields } => {
+                return self.respond_with_fields(ctx, command, &title, fields).await;
+            }
+            action => action,
+        };
+
+        let mut chunks =
+            responses::chunk_lines(&response.content, responses::MAX_MESSAGE_LEN).into_iter();
+        let first = chunks.next().unwrap_or_default();
+
+        let result = command
+            .create_interaction_response(&ctx.http, |interaction_response| {
+                interaction_response
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| {
+                        message.content(first);
+                        apply_action(message, &action)
+                    })
+            })
+            .await;
+
+        if let Err(e) = result {
+            return log::error!("Unable to respond to command: {e}");
+        }
+
+        for chunk in chunks {
+            let result = command
+                .create_followup_message(&ctx.http, |message| message.content(chunk))
+                .await;
+
+            if let Err(e) = result {
+                log::error!("Unable to send followup for {}: {e}", command.data.name);
+            }
+        }
+    }
+
+    /// Sends `fields` as one or more embeds, paginating across followups when there are
+    /// more than `responses::MAX_EMBED_FIELDS` of them.
+    async fn respond_with_fields(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        title: &str,
+        fields: Vec<(String, String)>,
+    ) {
+        let mut pages = responses::paginate_fields(fields, responses::MAX_EMBED_FIELDS).into_iter();
+        let first = pages.next().unwrap_or_default();
+
+        let result = command
+            .create_interaction_response(&ctx.http, |interaction_response| {
+                interaction_response
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| {
+                        message.embed(|e| e.title(title).fields(field_tuples(first)))
+                    })
+            })
+            .await;
+
+        if let Err(e) = result {
+            return log::error!("Unable to respond to command: {e}");
+        }
+
+        for page in pages {
+            let result = command
+                .create_followup_message(&ctx.http, |message| {
+                    message.embed(|e| e.title(title).fields(field_tuples(page)))
+                })
+                .await;
+
+            if let Err(e) = result {
+                log::error!("Unable to send followup for {}: {e}", command.data.name);
+            }
+        }
+    }
+
+    async fn handle_message_component(
+        &self,
+        ctx: Context,
+        component: MessageComponentInteraction,
+    ) {
+        let custom_id = component.data.custom_id.clone();
+
+        // Every component currently in use belongs to the `go` command, so both its
+        // components (and only them) are gated on `go`'s authorization.
+        if let Some(denial) = self.component_denial(&component, "go") {
+            return self.deny_component(&ctx, &component, denial).await;
+        }
+
+        if let Some(shortcut) = custom_id.strip_prefix(go::COPY_BUTTON_PREFIX) {
+            self.respond_go_copy(ctx, component, shortcut).await;
+        } else if custom_id == go::SELECT_MENU_ID {
+            self.respond_go_select(ctx, component).await;
+        } else {
+            log::warn!("Unknown component interaction: {custom_id}");
+        }
+    }
+
+    /// Re-checks authorization for a component click: only the member who originally
+    /// invoked the command may use its components, and they must still pass the same
+    /// role check `commands::before` runs for `command_name`. Without this, anyone who
+    /// can see a public response (e.g. a "no link found" shortcut picker) could click
+    /// through it regardless of whether they're allowed to run the command themselves.
+    fn component_denial(
+        &self,
+        component: &MessageComponentInteraction,
+        command_name: &str,
+    ) -> Option<String> {
+        let original_invoker = component.message.interaction.as_ref().map(|i| i.user.id);
+        if original_invoker.is_some_and(|id| id != component.user.id) {
+            return Some("Only the person who ran this command can use it".to_string());
+        }
+
+        let caller_roles = component
+            .member
+            .as_ref()
+            .map(|member| member.roles.clone())
+            .unwrap_or_default();
+
+        commands::check_roles(command_name, &caller_roles, &self.config.read())
+    }
+
+    async fn deny_component(
+        &self,
+        ctx: &Context,
+        component: &MessageComponentInteraction,
+        denial: String,
+    ) {
+        let result = component
+            .create_interaction_response(&ctx.http, |response| {
+                response
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| message.content(denial).ephemeral(true))
+            })
+            .await;
+
+        if let Err(e) = result {
+            log::error!("Unable to respond to component: {e}");
+        }
+    }
+
+    async fn respond_go_copy(
+        &self,
+        ctx: Context,
+        component: MessageComponentInteraction,
+        shortcut: &str,
+    ) {
+        let content = match self.link_store.get(shortcut) {
+            Ok(Some(link)) => link,
+            Ok(None) => format!("No link registered under `{shortcut}`"),
+            Err(e) => {
+                log::error!("Link store error: {e}");
+                "Server Error: Unable to fetch link :(".to_string()
+            }
+        };
+
+        let result = component
+            .create_interaction_response(&ctx.http, |response| {
+                response
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| message.content(content).ephemeral(true))
+            })
+            .await;
+
+        if let Err(e) = result {
+            log::error!("Unable to respond to component: {e}");
+        }
+    }
+
+    async fn respond_go_select(&self, ctx: Context, component: MessageComponentInteraction) {
+        let Some(shortcut) = component.data.values.get(0) else {
+            return log::error!("Select menu interaction with no selected value");
+        };
+
+        let content = match self.link_store.get(shortcut) {
+            Ok(Some(link)) => link,
+            Ok(None) => format!("No link registered under `{shortcut}`"),
+            Err(e) => {
+                log::error!("Link store error: {e}");
+                "Server Error: Unable to fetch link :(".to_string()
+            }
+        };
+
+        let result = component
+            .create_interaction_response(&ctx.http, |response| {
+                response
+                    .kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|message| message.content(content))
+            })
+            .await;
+
+        if let Err(e) = result {
+            log::error!("Unable to respond to component: {e}");
+        }
+    }
+}
+
+/// Adapts `(name, value)` pairs into the `(name, value, inline)` tuples serenity's
+/// `CreateEmbed::fields` expects, truncating each to Discord's per-field length limits.
+fn field_tuples(fields: Vec<(String, String)>) -> Vec<(String, String, bool)> {
+    fields
+        .into_iter()
+        .map(|(name, value)| {
+            let name = responses::truncate(&name, responses::MAX_EMBED_FIELD_NAME_LEN);
+            let value = responses::truncate(&value, responses::MAX_EMBED_FIELD_VALUE_LEN);
+            (name.to_string(), value.to_string(), false)
+        })
+        .collect()
+}
+
+/// Attaches whatever message components `action` calls for to an interaction response.
+fn apply_action<'a>(
+    message: &'a mut serenity::builder::CreateInteractionResponseData,
+    action: &commands::Action,
+) -> &'a mut serenity::builder::CreateInteractionResponseData {
+    match action {
+        commands::Action::None => message,
+        commands::Action::CopyLink { shortcut } => {
+            let custom_id = format!("{}{shortcut}", go::COPY_BUTTON_PREFIX);
+            message.components(|c| {
+                c.create_action_row(|row| {
+                    row.create_button(|button| {
+                        button
+                            .custom_id(custom_id)
+                            .label("Copy link")
+                            .style(serenity::model::prelude::component::ButtonStyle::Secondary)
+                    })
+                })
+            })
+        }
+        commands::Action::SelectExisting { shortcuts } => message.components(|c| {
+            c.create_action_row(|row| {
+                row.create_select_menu(|menu| {
+                    menu.custom_id(go::SELECT_MENU_ID)
+                        .placeholder("Pick an existing shortcut")
+                        .options(|options| {
+                            for shortcut in shortcuts {
+                                options.create_option(|option| {
+                                    option.label(shortcut).value(shortcut)
+                                });
+                            }
+                            options
+                        })
+                })
+            })
+        }),
+        commands::Action::Fields { .. } => message,
     }
 }
 
@@ -200,19 +630,3 @@ fn is_pin_emoji(reaction_type: ReactionType) -> bool {
         _ => false,
     }
 }
-
-const CODE_FMT_MSG: &str = r#"
-Please post your code examples and compiler output with code fences (\`\`\`) around them. Example:
-\`\`\`rust
-let (x, y) = (0, 42);
-println!("Position at {}, {}", x, y);
-\`\`\`
-
-```rust
-let (x, y) = (0, 42);
-println!("Position at {}, {}", x, y);
-```
-
-If the snippet is long or you want to demonstrate something, consider sharing it through the playground: <https://play.rust-lang.org/> or <https://www.rustexplorer.com/> or <https://paste.rs/web>.
-Please avoid sharing screenshots of your code, as they're not very accessible. Using code fences or a shared snippet makes the code more readable and allows those helping you to copy-paste the code to help explain things.
-"#;
@@ -0,0 +1,49 @@
+use super::{Command, Response, State};
+
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::prelude::interaction::application_command::ApplicationCommandInteraction;
+use serenity::prelude::Context;
+
+pub struct Codefmt;
+
+#[async_trait]
+impl Command for Codefmt {
+    fn name(&self) -> &'static str {
+        "codefmt"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command
+            .name("codefmt")
+            .description("Display a message showing how to share code samples")
+    }
+
+    async fn run(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+        _state: &State,
+    ) -> Response {
+        Response::text(CODE_FMT_MSG)
+    }
+}
+
+const CODE_FMT_MSG: &str = r#"
+Please post your code examples and compiler output with code fences (\`\`\`) around them. Example:
+\`\`\`rust
+let (x, y) = (0, 42);
+println!("Position at {}, {}", x, y);
+\`\`\`
+
+```rust
+let (x, y) = (0, 42);
+println!("Position at {}, {}", x, y);
+```
+
+If the snippet is long or you want to demonstrate something, consider sharing it through the playground: <https://play.rust-lang.org/> or <https://www.rustexplorer.com/> or <https://paste.rs/web>.
+Please avoid sharing screenshots of your code, as they're not very accessible. Using code fences or a shared snippet makes the code more readable and allows those helping you to copy-paste the code to help explain things.
+"#;
@@ -0,0 +1,126 @@
+use super::{Action, Command, Response, State};
+
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::prelude::command::CommandOptionType;
+use serenity::model::prelude::interaction::application_command::{
+    ApplicationCommandInteraction, CommandDataOption, CommandDataOptionValue,
+};
+use serenity::prelude::Context;
+
+/// custom_id prefix for the "copy link" button attached to a `go` response, followed by
+/// the shortcut name, e.g. `go_copy:wiki`.
+pub const COPY_BUTTON_PREFIX: &str = "go_copy:";
+
+/// custom_id of the select menu offering existing shortcuts when a lookup misses.
+pub const SELECT_MENU_ID: &str = "go_select";
+
+/// Discord hard-limits a select menu to this many options.
+const SELECT_MENU_OPTION_LIMIT: usize = 25;
+
+pub struct Go;
+
+#[async_trait]
+impl Command for Go {
+    fn name(&self) -> &'static str {
+        "go"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        register(command)
+    }
+
+    async fn run(
+        &self,
+        _ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        state: &State,
+    ) -> Response {
+        run(&command.data.options, state.link_store)
+    }
+}
+
+pub fn run(options: &[CommandDataOption], link_store: &kv::Store<String>) -> Response {
+    let shortcut = get_shortcut(options).unwrap();
+    match get_link(options) {
+        Some(link) => match link_store.set(&shortcut, &link) {
+            Ok(()) => Response::new(
+                format!("{link} was registered under {shortcut}!"),
+                Action::CopyLink { shortcut },
+            ),
+            Err(e) => {
+                log::error!("Link store error: {e}");
+                Response::new("Server Error: Unable to register link :(", Action::None)
+            }
+        },
+        None => match link_store.get(&shortcut) {
+            Ok(Some(link)) => Response::new(link, Action::CopyLink { shortcut }),
+            Ok(None) => Response::new(
+                format!("No link registered under `{shortcut}`"),
+                existing_shortcuts_action(link_store),
+            ),
+            Err(e) => {
+                log::error!("Link store error: {e}");
+                Response::new("Server Error: Unable to fetch link :(", Action::None)
+            }
+        },
+    }
+}
+
+fn existing_shortcuts_action(link_store: &kv::Store<String>) -> Action {
+    match link_store.to_map() {
+        Ok(map) => {
+            let mut shortcuts: Vec<_> = map.into_keys().collect();
+            shortcuts.sort();
+            if shortcuts.len() > SELECT_MENU_OPTION_LIMIT {
+                log::info!(
+                    "{} shortcuts registered, only showing the first {SELECT_MENU_OPTION_LIMIT} in the picker",
+                    shortcuts.len()
+                );
+                shortcuts.truncate(SELECT_MENU_OPTION_LIMIT);
+            }
+            Action::SelectExisting { shortcuts }
+        }
+        Err(e) => {
+            log::error!("Link store error: {e}");
+            Action::None
+        }
+    }
+}
+
+fn get_shortcut(options: &[CommandDataOption]) -> Option<String> {
+    match options.get(0).and_then(|opt| opt.resolved.as_ref()) {
+        Some(CommandDataOptionValue::String(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn get_link(options: &[CommandDataOption]) -> Option<String> {
+    match options.get(1).and_then(|opt| opt.resolved.as_ref()) {
+        Some(CommandDataOptionValue::String(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("go")
+        .description("Link shortener")
+        .create_option(|option| {
+            option
+                .name("shortcut")
+                .description("The name of the shortcut")
+                .kind(CommandOptionType::String)
+                .required(true)
+        })
+        .create_option(|option| {
+            option
+                .name("link")
+                .description("The link")
+                .kind(CommandOptionType::String)
+                .required(false)
+        })
+}
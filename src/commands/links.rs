@@ -0,0 +1,55 @@
+use super::{Action, Command, Response, State};
+
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::prelude::interaction::application_command::ApplicationCommandInteraction;
+use serenity::prelude::Context;
+
+pub struct Links;
+
+#[async_trait]
+impl Command for Links {
+    fn name(&self) -> &'static str {
+        "links"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command
+            .name("links")
+            .description("List every shortcut registered with the go command")
+    }
+
+    async fn run(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+        state: &State,
+    ) -> Response {
+        run(state.link_store)
+    }
+}
+
+pub fn run(link_store: &kv::Store<String>) -> Response {
+    match link_store.to_map() {
+        Ok(map) if map.is_empty() => Response::text("No links registered yet"),
+        Ok(map) => {
+            let mut fields: Vec<_> = map.into_iter().collect();
+            fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            Response::new(
+                String::new(),
+                Action::Fields {
+                    title: "Registered shortcuts".to_string(),
+                    fields,
+                },
+            )
+        }
+        Err(e) => {
+            log::error!("Link store error: {e}");
+            Response::text("Server Error: Unable to list links :(")
+        }
+    }
+}
@@ -0,0 +1,119 @@
+pub mod codefmt;
+pub mod go;
+pub mod links;
+pub mod ping;
+pub mod remind;
+
+use crate::config::Config;
+
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::prelude::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::prelude::RoleId;
+use serenity::prelude::Context;
+
+/// Extra message components a command's response should carry, e.g. the `go` command's
+/// copy-link button or shortcut picker.
+pub enum Action {
+    None,
+    CopyLink { shortcut: String },
+    SelectExisting { shortcuts: Vec<String> },
+    /// Renders the response as embed fields instead of plain text, paginating across
+    /// followups when there are more than `responses::MAX_EMBED_FIELDS` of them.
+    Fields { title: String, fields: Vec<(String, String)> },
+}
+
+pub struct Response {
+    pub content: String,
+    pub action: Action,
+}
+
+impl Response {
+    pub fn new(content: impl Into<String>, action: Action) -> Self {
+        Response {
+            content: content.into(),
+            action,
+        }
+    }
+
+    pub fn text(content: impl Into<String>) -> Self {
+        Response::new(content, Action::None)
+    }
+}
+
+/// The stores and other shared state a command's `run` may need.
+pub struct State<'a> {
+    pub link_store: &'a kv::Store<String>,
+    pub reminder_store: &'a kv::Store<remind::Reminder>,
+    pub config: &'a Config,
+}
+
+/// A self-registering slash command. Implementing this and adding the type to `registry`
+/// is the only wiring a new command needs: `ready` uses `register` to declare it with
+/// Discord, and `interaction_create` dispatches to `run` by matching `name`.
+#[async_trait]
+pub trait Command: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand;
+
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        state: &State,
+    ) -> Response;
+}
+
+pub fn registry() -> Vec<Box<dyn Command>> {
+    vec![
+        Box::new(ping::Ping),
+        Box::new(codefmt::Codefmt),
+        Box::new(go::Go),
+        Box::new(remind::Remind),
+        Box::new(links::Links),
+    ]
+}
+
+/// Runs before every command invocation: logs it, then checks the caller's roles against
+/// `Config::command_roles` (a command absent from the map, or mapped to an empty list, is
+/// open to everyone). Returns `Some(denial message)` to short-circuit the command.
+pub fn before(
+    command: &ApplicationCommandInteraction,
+    caller_roles: &[RoleId],
+    config: &Config,
+) -> Option<String> {
+    log::info!(
+        "Received {} command from {}",
+        command.data.name,
+        command.user.name
+    );
+
+    check_roles(command.data.name.as_str(), caller_roles, config)
+}
+
+/// Checks `caller_roles` against `Config::command_roles` for `command_name` (a command
+/// absent from the map, or mapped to an empty list, is open to everyone). Returns
+/// `Some(denial message)` to short-circuit the command. Shared by `before` and by
+/// `Handler::handle_message_component`, which re-checks it for the command that produced
+/// the component being clicked.
+pub fn check_roles(command_name: &str, caller_roles: &[RoleId], config: &Config) -> Option<String> {
+    let allowed_roles = config.command_roles.get(command_name)?;
+
+    if allowed_roles.is_empty() || caller_roles.iter().any(|role| allowed_roles.contains(role)) {
+        None
+    } else {
+        Some("You don't have permission to use this command".to_string())
+    }
+}
+
+/// Runs after every command invocation, reporting failures that follow the repo's
+/// `Server Error: ...` response convention.
+pub fn after(command_name: &str, response: &Response) {
+    if response.content.starts_with("Server Error") {
+        log::error!("{command_name} command failed: {}", response.content);
+    }
+}
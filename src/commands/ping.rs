@@ -0,0 +1,31 @@
+use super::{Command, Response, State};
+
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::prelude::interaction::application_command::ApplicationCommandInteraction;
+use serenity::prelude::Context;
+
+pub struct Ping;
+
+#[async_trait]
+impl Command for Ping {
+    fn name(&self) -> &'static str {
+        "ping"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command.name("ping").description("A ping command")
+    }
+
+    async fn run(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+        _state: &State,
+    ) -> Response {
+        Response::text("pong")
+    }
+}
@@ -0,0 +1,362 @@
+use super::{Command, Response, State};
+
+use crate::config::Feature;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::prelude::command::CommandOptionType;
+use serenity::model::prelude::interaction::application_command::{
+    ApplicationCommandInteraction, CommandDataOption, CommandDataOptionValue,
+};
+use serenity::model::prelude::{ChannelId, UserId};
+use serenity::prelude::Context;
+
+/// How often the dispatcher checks the store for reminders that have come due.
+const DISPATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub channel_id: ChannelId,
+    pub user_id: UserId,
+    pub message: String,
+    pub fire_at: u64,
+}
+
+pub struct Remind;
+
+#[async_trait]
+impl Command for Remind {
+    fn name(&self) -> &'static str {
+        "remind"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        register(command)
+    }
+
+    async fn run(
+        &self,
+        _ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        state: &State,
+    ) -> Response {
+        if !state.config.is_enabled(Feature::Reminders) {
+            return Response::text("The reminders feature is currently disabled");
+        }
+
+        Response::text(run(
+            command.channel_id,
+            command.user.id,
+            &command.data.options,
+            state.reminder_store,
+        ))
+    }
+}
+
+pub fn run(
+    channel_id: ChannelId,
+    user_id: UserId,
+    options: &[CommandDataOption],
+    reminder_store: &kv::Store<Reminder>,
+) -> String {
+    let now = unix_now();
+    let Some(duration) = get_duration(options, now) else {
+        return "Unable to parse duration, try something like `10m`, `2h30m`, or `tomorrow 9am`"
+            .to_string();
+    };
+    let Some(message) = get_message(options) else {
+        return "A reminder needs a message".to_string();
+    };
+
+    let reminder = Reminder {
+        channel_id,
+        user_id,
+        message,
+        fire_at: now + duration.as_secs(),
+    };
+
+    match reminder_store.set(&generate_key(), &reminder) {
+        Ok(()) => format!("Got it, I'll remind you in {}", get_raw_duration(options)),
+        Err(e) => {
+            log::error!("Reminder store error: {e}");
+            "Server Error: Unable to schedule reminder :(".to_string()
+        }
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("remind")
+        .description("Schedule a reminder")
+        .create_option(|option| {
+            option
+                .name("in")
+                .description("When to be reminded, e.g. `10m` or `2h30m`")
+                .kind(CommandOptionType::String)
+                .required(true)
+        })
+        .create_option(|option| {
+            option
+                .name("message")
+                .description("What to be reminded of")
+                .kind(CommandOptionType::String)
+                .required(true)
+        })
+}
+
+/// Periodically scans `reminder_store` and delivers any reminder whose `fire_at` has passed.
+pub async fn spawn_dispatcher(ctx: Context, reminder_store: kv::Store<Reminder>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DISPATCH_INTERVAL);
+        loop {
+            interval.tick().await;
+            dispatch_due_reminders(&ctx, &reminder_store).await;
+        }
+    });
+}
+
+async fn dispatch_due_reminders(ctx: &Context, reminder_store: &kv::Store<Reminder>) {
+    let reminders = match reminder_store.to_map() {
+        Ok(reminders) => reminders,
+        Err(e) => return log::error!("Reminder store error: {e}"),
+    };
+
+    let now = unix_now();
+    for (key, reminder) in reminders {
+        if reminder.fire_at > now {
+            continue;
+        }
+
+        let content = format!("<@{}> :alarm_clock: {}", reminder.user_id, reminder.message);
+        if let Err(e) = reminder.channel_id.say(&ctx.http, content).await {
+            log::error!("Unable to send reminder: {e}");
+        }
+
+        if let Err(e) = reminder_store.unset(&key) {
+            log::error!("Reminder store error: {e}");
+        }
+    }
+}
+
+fn get_duration(options: &[CommandDataOption], now: u64) -> Option<Duration> {
+    parse_duration(&get_raw_duration(options), now)
+}
+
+fn get_raw_duration(options: &[CommandDataOption]) -> String {
+    match options.get(0).and_then(|opt| opt.resolved.as_ref()) {
+        Some(CommandDataOptionValue::String(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+fn get_message(options: &[CommandDataOption]) -> Option<String> {
+    match options.get(1).and_then(|opt| opt.resolved.as_ref()) {
+        Some(CommandDataOptionValue::String(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+const SECS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// Parses either a relative duration such as `10m`/`2h30m`, or an absolute phrase such as
+/// `today 9am`/`tomorrow 9am`, resolving the latter against `now` (unix seconds, UTC) into
+/// the `Duration` remaining until it fires.
+fn parse_duration(input: &str, now: u64) -> Option<Duration> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    if let Some(duration) = parse_relative_duration(input) {
+        return Some(duration);
+    }
+
+    parse_absolute_duration(input, now)
+}
+
+/// Splits `10m`/`2h30m` into number+unit tokens and sums the seconds they represent.
+fn parse_relative_duration(input: &str) -> Option<Duration> {
+    let mut seconds: u64 = 0;
+    let mut number = String::new();
+    let mut unit = String::new();
+
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            if !unit.is_empty() {
+                seconds += unit_seconds(&number, &unit)?;
+                number.clear();
+                unit.clear();
+            }
+            number.push(c);
+        } else if !c.is_whitespace() {
+            unit.push(c);
+        }
+    }
+
+    seconds += unit_seconds(&number, &unit)?;
+
+    Some(Duration::from_secs(seconds))
+}
+
+fn unit_seconds(number: &str, unit: &str) -> Option<u64> {
+    let number: u64 = number.parse().ok()?;
+    let multiplier = match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 60 * 60,
+        "d" | "day" | "days" => 60 * 60 * 24,
+        "w" | "week" | "weeks" => 60 * 60 * 24 * 7,
+        _ => return None,
+    };
+    number.checked_mul(multiplier)
+}
+
+/// Handles `today`/`tomorrow` followed by a clock time (`9am`, `9:30am`, `21:00`),
+/// resolved as UTC against `now`. `today` rolls over to tomorrow if the time has
+/// already passed.
+fn parse_absolute_duration(input: &str, now: u64) -> Option<Duration> {
+    let lower = input.to_ascii_lowercase();
+    let mut parts = lower.splitn(2, ' ');
+    let day = parts.next()?;
+    let time = parts.next()?;
+
+    let day_offset: u64 = match day {
+        "today" => 0,
+        "tomorrow" => 1,
+        _ => return None,
+    };
+    let (hour, minute) = parse_clock(time)?;
+
+    let day_start = now - (now % SECS_PER_DAY);
+    let mut fire_at = day_start + day_offset * SECS_PER_DAY + hour * 3600 + minute * 60;
+    if day_offset == 0 && fire_at <= now {
+        fire_at += SECS_PER_DAY;
+    }
+
+    Some(Duration::from_secs(fire_at.saturating_sub(now)))
+}
+
+/// Parses a 12-hour (`9am`, `9:30pm`) or 24-hour (`21:00`) clock time into `(hour, minute)`.
+fn parse_clock(time: &str) -> Option<(u64, u64)> {
+    let (digits, pm) = if let Some(d) = time.strip_suffix("am") {
+        (d, false)
+    } else if let Some(d) = time.strip_suffix("pm") {
+        (d, true)
+    } else {
+        (time, false)
+    };
+
+    let mut split = digits.splitn(2, ':');
+    let mut hour: u64 = split.next()?.parse().ok()?;
+    let minute: u64 = match split.next() {
+        Some(m) => m.parse().ok()?,
+        None => 0,
+    };
+
+    if time.ends_with("am") || time.ends_with("pm") {
+        hour = if hour == 12 { 0 } else { hour };
+        if pm {
+            hour += 12;
+        }
+    }
+
+    if hour < 24 && minute < 60 {
+        Some((hour, minute))
+    } else {
+        None
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+static REMINDER_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Generates a unique key from the current time in nanoseconds and a process-wide counter,
+/// using only characters that `kv`'s key alphabet allows.
+fn generate_key() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let counter = REMINDER_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{nanos} {counter}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn reminder_round_trips_through_the_store() {
+        let f = NamedTempFile::new().unwrap();
+        let store = kv::Store::<Reminder>::open(f.path()).unwrap();
+
+        let reminder = Reminder {
+            channel_id: ChannelId(111),
+            user_id: UserId(222),
+            message: "feed the cat".to_string(),
+            fire_at: 1_700_000_000,
+        };
+        store.set(&generate_key(), &reminder).unwrap();
+
+        let reminders = store.to_map().unwrap();
+        assert_eq!(1, reminders.len());
+        assert_eq!(
+            &reminder.message,
+            &reminders.values().next().unwrap().message
+        );
+    }
+
+    #[test]
+    fn parses_relative_durations() {
+        assert_eq!(Some(Duration::from_secs(600)), parse_duration("10m", 0));
+        assert_eq!(
+            Some(Duration::from_secs(2 * 3600 + 30 * 60)),
+            parse_duration("2h30m", 0)
+        );
+    }
+
+    #[test]
+    fn parses_tomorrow_at_a_given_time() {
+        // 2023-11-14 12:00:00 UTC
+        let now = 1_699_963_200;
+        let duration = parse_duration("tomorrow 9am", now).unwrap();
+        let fire_at = now + duration.as_secs();
+
+        // 2023-11-15 09:00:00 UTC
+        assert_eq!(1_700_038_800, fire_at);
+    }
+
+    #[test]
+    fn rolls_today_over_to_tomorrow_once_the_time_has_passed() {
+        // 2023-11-14 12:00:00 UTC
+        let now = 1_699_963_200;
+        let duration = parse_duration("today 9am", now).unwrap();
+        let fire_at = now + duration.as_secs();
+
+        // 2023-11-15 09:00:00 UTC
+        assert_eq!(1_700_038_800, fire_at);
+    }
+
+    #[test]
+    fn parses_12am_as_midnight_and_12pm_as_noon() {
+        assert_eq!(Some((0, 0)), parse_clock("12am"));
+        assert_eq!(Some((12, 0)), parse_clock("12pm"));
+        assert_eq!(Some((0, 30)), parse_clock("12:30am"));
+        assert_eq!(Some((12, 30)), parse_clock("12:30pm"));
+    }
+}
@@ -1,6 +1,7 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use serenity::model::prelude::{ChannelId, RoleId};
@@ -26,6 +27,12 @@ pub const DEFAULT_PATH: &str = "/etc/megabot/config.toml";
 pub enum Feature {
     /// Pinning/unpinning messages through an emoji on behalf of contributors.
     Pins,
+
+    /// Scheduling and delivering `/remind` reminders.
+    Reminders,
+
+    /// Detecting and reporting ghost pings (mention messages that get deleted or edited).
+    GhostPings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +45,17 @@ pub struct Config {
 
     /// Currently enabled feature flags.
     pub enabled_features: Vec<Feature>,
+
+    /// Path to the append-only store backing the `go` link shortener.
+    pub link_store_path: PathBuf,
+
+    /// Path to the append-only store backing `/remind` reminders.
+    pub reminder_store_path: PathBuf,
+
+    /// Role IDs allowed to invoke each command, keyed by command name. A command absent
+    /// from this map, or mapped to an empty list, is open to everyone.
+    #[serde(default)]
+    pub command_roles: HashMap<String, Vec<RoleId>>,
 }
 
 impl Config {
@@ -47,4 +65,8 @@ impl Config {
         file.read_to_string(&mut contents)?;
         toml::from_str(&contents).map_err(ConfigError::from)
     }
+
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.enabled_features.contains(&feature)
+    }
 }
@@ -1,5 +1,8 @@
 mod bot;
+mod commands;
 mod config;
+mod message_cache;
+mod responses;
 
 use crate::config::Config;
 
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use serenity::model::prelude::{ChannelId, MessageId, RoleId, UserId};
+
+/// A message that mentioned at least one user or role, kept around just long enough to
+/// notice if it gets deleted or edited to strip those mentions.
+#[derive(Debug, Clone)]
+pub struct CachedMessage {
+    pub author_id: UserId,
+    pub channel_id: ChannelId,
+    pub content: String,
+    pub mentioned_users: Vec<UserId>,
+    pub mentioned_roles: Vec<RoleId>,
+}
+
+/// A bounded, FIFO-evicted cache of recent mention-carrying messages, keyed by message id.
+pub struct MessageCache {
+    capacity: usize,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    messages: FxHashMap<MessageId, CachedMessage>,
+    order: VecDeque<MessageId>,
+}
+
+impl MessageCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        MessageCache {
+            capacity,
+            inner: Mutex::new(Inner {
+                messages: FxHashMap::default(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+
+    pub fn insert(&self, id: MessageId, message: CachedMessage) {
+        let mut inner = self.inner.lock();
+
+        if inner.messages.insert(id, message).is_none() {
+            inner.order.push_back(id);
+        }
+
+        while inner.order.len() > self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.messages.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn get(&self, id: &MessageId) -> Option<CachedMessage> {
+        self.inner.lock().messages.get(id).cloned()
+    }
+
+    pub fn remove(&self, id: &MessageId) -> Option<CachedMessage> {
+        let mut inner = self.inner.lock();
+        inner.order.retain(|cached_id| cached_id != id);
+        inner.messages.remove(id)
+    }
+}
@@ -0,0 +1,140 @@
+/// Discord's hard limit on a single message's content length.
+pub const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Discord's hard limit on the number of fields a single embed can carry.
+pub const MAX_EMBED_FIELDS: usize = 25;
+
+/// Discord's hard limit on an embed field's name length.
+pub const MAX_EMBED_FIELD_NAME_LEN: usize = 256;
+
+/// Discord's hard limit on an embed field's value length.
+pub const MAX_EMBED_FIELD_VALUE_LEN: usize = 1024;
+
+/// Truncates `text` to at most `max_len` bytes, respecting UTF-8 character boundaries.
+pub fn truncate(text: &str, max_len: usize) -> &str {
+    if text.len() <= max_len {
+        return text;
+    }
+
+    let mut end = max_len;
+    while end > 0 && !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Groups `fields` into pages of at most `max_fields`, so a long listing can be sent as a
+/// sequence of embeds instead of being rejected or truncated.
+pub fn paginate_fields<T>(fields: Vec<T>, max_fields: usize) -> Vec<Vec<T>> {
+    let mut pages = Vec::new();
+    let mut fields = fields.into_iter().peekable();
+
+    while fields.peek().is_some() {
+        pages.push(fields.by_ref().take(max_fields).collect());
+    }
+
+    pages
+}
+
+/// Splits `text` on line boundaries into chunks no longer than `max_len`, so a long
+/// response can be sent as a sequence of messages instead of being rejected or truncated.
+/// A single line longer than `max_len` is itself split, respecting UTF-8 boundaries.
+pub fn chunk_lines(text: &str, max_len: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        for piece in split_oversized_line(line, max_len) {
+            let fits = current.is_empty() || current.len() + 1 + piece.len() <= max_len;
+            if !fits {
+                chunks.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(piece);
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+fn split_oversized_line(line: &str, max_len: usize) -> Vec<&str> {
+    if line.len() <= max_len {
+        return vec![line];
+    }
+
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    while start < line.len() {
+        let mut end = (start + max_len).min(line.len());
+        while end < line.len() && !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        pieces.push(&line[start..end]);
+        start = end;
+    }
+
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_does_not_split_a_multi_byte_character() {
+        // Each "é" is 2 bytes, so a 5-byte cap lands in the middle of the 3rd one.
+        let text = "ééé";
+        assert_eq!("éé", truncate(text, 5));
+    }
+
+    #[test]
+    fn truncate_leaves_short_text_untouched() {
+        assert_eq!("hi", truncate("hi", 5));
+    }
+
+    #[test]
+    fn split_oversized_line_does_not_split_a_multi_byte_character() {
+        // Each "é" is 2 bytes, so a 5-byte cap lands in the middle of the 3rd one; that
+        // character should be pushed whole into the next piece.
+        let line = "ééé";
+        let pieces = split_oversized_line(line, 5);
+
+        assert_eq!(vec!["éé", "é"], pieces);
+        assert_eq!(line, pieces.concat());
+    }
+
+    #[test]
+    fn chunk_lines_packs_exactly_up_to_max_len() {
+        // "12345\n1234" is exactly 10 bytes, the configured max, so it should stay as one
+        // chunk; one more character pushes the second line into its own chunk.
+        assert_eq!(vec!["12345\n1234"], chunk_lines("12345\n1234", 10));
+        assert_eq!(
+            vec!["12345".to_string(), "12345".to_string()],
+            chunk_lines("12345\n12345", 10)
+        );
+    }
+
+    #[test]
+    fn chunk_lines_splits_an_oversized_line_at_a_utf8_boundary() {
+        let chunks = chunk_lines("ééé", 5);
+        assert_eq!(vec!["éé", "é"], chunks);
+    }
+
+    #[test]
+    fn paginate_fields_fills_a_page_exactly_before_starting_a_new_one() {
+        let fields: Vec<u32> = (0..25).collect();
+        assert_eq!(1, paginate_fields(fields, 25).len());
+
+        let fields: Vec<u32> = (0..26).collect();
+        let pages = paginate_fields(fields, 25);
+        assert_eq!(2, pages.len());
+        assert_eq!(25, pages[0].len());
+        assert_eq!(1, pages[1].len());
+    }
+}